@@ -1,14 +1,27 @@
 use android_bluetooth_serial::{self, BluetoothDevice, BluetoothSocket};
 use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, io::{Read, Write}, sync::Arc, thread};
+use std::{
+    collections::{HashMap, HashSet},
+    io::{Read, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::Duration,
+};
 use tauri::{Manager, State}; // Tauri 상태 관리 및 이벤트 발행을 위해 필요
 
 // 프런트엔드로 보낼 장치 정보를 담을 구조체 (Serialize 가능해야 함)
+// rssi는 탐색(discovery) 결과에서만 의미 있는 값이며, 페어링된 장치 목록에서는
+// rssi를 알 수 없으므로 i16::MIN을 센티널로 사용한다 (이때 bonded는 항상 true).
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct Device {
     address: String,
     name: String,
+    rssi: i16,
+    bonded: bool,
 }
 
 // 프런트엔드로 보낼 수신 데이터 및 상태 변경 정보를 담을 구조체
@@ -16,20 +29,154 @@ struct Device {
 struct Payload {
     address: String, // 어떤 장치로부터의 데이터인지 식별
     data: Vec<u8>,
+    // BLE GATT 알림(subscribe)에서 온 데이터인 경우 발신 특성(characteristic) UUID.
+    // 클래식 RFCOMM 데이터에는 특성 개념이 없으므로 None.
+    char_uuid: Option<String>,
 }
 
 #[derive(Clone, Serialize)]
 struct StatusPayload {
     address: String,
-    status: String, // "connected", "disconnected", "error: ..." 등
+    status: String, // "connected", "disconnected", "reconnecting", "error: ..." 등
     error: Option<String>,
+    // "reconnecting" 상태에서만 채워지는 재시도 횟수 (1부터 시작)
+    attempt: Option<u32>,
+}
+
+// 연결별 메시지 프레이밍 방식. 읽기 스레드가 원시 바이트 스트림에서 완전한 메시지 단위를
+// 잘라내는 방법을 정한다. 지정하지 않으면 Raw(기존 그대로 읽은 만큼 즉시 전달)로 동작한다.
+#[derive(Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum FramingConfig {
+    // 기존 동작: socket.read가 돌려준 바이트를 그대로 한 건의 데이터로 전달
+    Raw,
+    // 구분자 바이트(예: b'\n')가 나올 때까지 누적했다가 구분자를 제외하고 한 건으로 전달
+    Delimiter { byte: u8 },
+    // header_size(2 또는 4)바이트의 리틀엔디안 길이 헤더 + 그 길이만큼의 페이로드
+    LengthPrefixed { header_size: u8, max_frame_len: u32 },
+}
+
+impl Default for FramingConfig {
+    fn default() -> Self {
+        FramingConfig::Raw
+    }
+}
+
+impl FramingConfig {
+    // connect_device_command가 연결을 맺기 전에 한 번 호출해서, 읽기 스레드가 매 읽기마다
+    // 같은 설정 오류로 재조립 버퍼만 계속 불리는 일을 막는다 (오버사이즈 프레임과 달리
+    // header_size 자체는 값이 바뀌지 않으므로 버퍼를 비워봐야 다음 읽기도 똑같이 실패한다).
+    fn validate(&self) -> Result<(), String> {
+        if let FramingConfig::LengthPrefixed { header_size, .. } = self {
+            if *header_size != 2 && *header_size != 4 {
+                return Err(format!("Unsupported header_size: {}", header_size));
+            }
+        }
+        Ok(())
+    }
+}
+
+// 재조립 버퍼에서 완성된 프레임들을 꺼낸다. 반환값은 (완성된 프레임 목록, 프레임 오류 메시지).
+// 오류가 발생해도 연결은 끊지 않고 버퍼만 비워 다음 프레임부터 다시 맞춘다.
+fn extract_frames(buf: &mut Vec<u8>, framing: &FramingConfig) -> (Vec<Vec<u8>>, Option<String>) {
+    let mut frames = Vec::new();
+
+    match framing {
+        FramingConfig::Raw => {
+            // Raw 모드는 호출하는 쪽에서 버퍼를 아예 쓰지 않고 바로 내보내므로 여기 도달하지 않는다.
+        }
+        FramingConfig::Delimiter { byte } => {
+            while let Some(pos) = buf.iter().position(|b| b == byte) {
+                let frame = buf.drain(..=pos).collect::<Vec<u8>>();
+                frames.push(frame[..frame.len() - 1].to_vec()); // 구분자 자체는 제외
+            }
+        }
+        FramingConfig::LengthPrefixed { header_size, max_frame_len } => {
+            let header_size = *header_size as usize;
+            loop {
+                if buf.len() < header_size {
+                    break; // 헤더조차 덜 도착함
+                }
+
+                let payload_len = match header_size {
+                    2 => u16::from_le_bytes([buf[0], buf[1]]) as usize,
+                    4 => u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize,
+                    _ => {
+                        // connect_device_command가 연결 전에 FramingConfig::validate로 걸러내므로 정상
+                        // 경로에서는 도달하지 않는다. 그래도 버퍼를 비워 무한히 쌓이는 일은 막는다.
+                        buf.clear();
+                        return (frames, Some(format!("Unsupported header_size: {}", header_size)));
+                    }
+                };
+
+                if payload_len as u32 > *max_frame_len {
+                    // 말도 안 되는 길이 - 동기화가 깨졌다고 보고 버퍼를 비워 다음 데이터부터 다시 맞춘다.
+                    buf.clear();
+                    return (frames, Some(format!(
+                        "Frame length {} exceeds max_frame_len {}, buffer reset",
+                        payload_len, max_frame_len
+                    )));
+                }
+
+                if buf.len() < header_size + payload_len {
+                    break; // 페이로드가 아직 다 도착하지 않음
+                }
+
+                let frame = buf[header_size..header_size + payload_len].to_vec();
+                buf.drain(..header_size + payload_len);
+                frames.push(frame);
+            }
+        }
+    }
+
+    (frames, None)
+}
+
+// 본딩(페어링) 상태 변화를 프런트엔드로 전달하는 페이로드
+#[derive(Clone, Serialize)]
+struct BondPayload {
+    address: String,
+    state: String, // "none", "bonding", "bonded"
+    error: Option<String>,
+}
+
+// android_bluetooth_serial::BondState를 프런트엔드에 노출할 문자열로 변환
+fn bond_state_to_str(state: android_bluetooth_serial::BondState) -> &'static str {
+    match state {
+        android_bluetooth_serial::BondState::None => "none",
+        android_bluetooth_serial::BondState::Bonding => "bonding",
+        android_bluetooth_serial::BondState::Bonded => "bonded",
+    }
 }
 
 
+// 개별 연결에 대해 관리되는 정보.
+// 읽기 스레드는 소켓을 락 없이 단독으로 소유하고, 쓰기는 writer_tx 채널로 전달되어
+// 전용 writer 스레드가 처리하므로 읽기/쓰기가 서로의 락을 기다리지 않는다.
+// 자동 재연결 여부(reconnect)는 connection_supervisor 스레드가 클로저로 캡처해서 직접 판단하므로
+// 맵에 저장된 항목에는 따로 들고 있지 않는다.
+// shutdown: 재연결 재시도 루프 중단 + reader/writer 스레드 종료 신호를 겸하는 플래그
+//           (수동 disconnect/cancel이 true로 설정하면 두 스레드가 짧은 타임아웃 내에 스스로 정리된다)
+struct ConnectionEntry {
+    writer_tx: mpsc::Sender<Vec<u8>>,
+    shutdown: Arc<AtomicBool>,
+}
+
+// BLE GATT 연결 하나를 감싸는 핸들. 클래식 RFCOMM(BluetoothSocket)과 별도로 관리된다.
+struct GattConnection {
+    device: android_bluetooth_serial::ble::BleDevice,
+}
+
 // Rust 백엔드에서 BluetoothSocket 인스턴스를 관리할 상태 구조체
-// HashMap: 장치 주소를 키로, BluetoothSocket (스레드 안전하게 공유)을 값으로 저장
+// HashMap: 장치 주소를 키로, 연결 정보(ConnectionEntry)를 값으로 저장
 struct AppState {
-    connections: RwLock<HashMap<String, Arc<Mutex<BluetoothSocket>>>>,
+    connections: RwLock<HashMap<String, ConnectionEntry>>,
+    // 탐색 스캔이 진행 중인지 여부. stop_discovery_command와 스캔 스레드가 함께 참조한다.
+    discovery_active: Arc<AtomicBool>,
+    // 탐색 스레드 핸들. 새 스캔을 시작할 때 이전 스레드가 합류(join)했는지 확인하는 데 쓴다.
+    discovery_handle: Mutex<Option<thread::JoinHandle<()>>>,
+    // BLE GATT 연결은 클래식 RFCOMM과 별개의 맵에 보관하여 두 링크 종류가 공존할 수 있게 한다.
+    gatt_connections: RwLock<HashMap<String, Arc<Mutex<GattConnection>>>>,
 }
 
 // AppState 초기화
@@ -37,6 +184,18 @@ impl Default for AppState {
     fn default() -> Self {
         Self {
             connections: RwLock::new(HashMap::new()),
+            discovery_active: Arc::new(AtomicBool::new(false)),
+            discovery_handle: Mutex::new(None),
+            gatt_connections: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+// 앱이 종료될 때 진행 중인 탐색 스캔을 정리한다.
+impl Drop for AppState {
+    fn drop(&mut self) {
+        if self.discovery_active.swap(false, Ordering::SeqCst) {
+            let _ = android_bluetooth_serial::stop_discovery();
         }
     }
 }
@@ -61,18 +220,178 @@ async fn get_bonded_devices_command() -> Result<Vec<Device>, String> {
                        let address = dev.get_address().map_err(|e| e.to_string())?;
                        let name = dev.get_name().unwrap_or_else(|_| "Unknown Device".to_string()); // 이름 가져오기 실패 시 대체
 
-                       Ok(Device { address, name })
+                       // 이미 페어링된 장치이므로 rssi는 알 수 없고(bonded=true) 센티널 값을 사용한다.
+                       Ok(Device { address, name, rssi: i16::MIN, bonded: true })
                    })
                    .collect::<Result<Vec<Device>, String>>()
         })
 }
 
+// 활성 탐색(inquiry) 스캔 시작
+// 새로 발견되는 장치마다 "bluetooth-discovery" 이벤트로 스트리밍하며,
+// 신호 세기(rssi)와 페어링 여부를 함께 실어 보내 프런트엔드가 정렬/구분할 수 있게 한다.
+#[tauri::command]
+async fn start_discovery_command(
+    app_handle: tauri::AppHandle,
+    state: State<'_, Arc<AppState>>,
+) -> Result<(), String> {
+    // 이미 스캔 중이면 중복 시작하지 않음
+    if state.discovery_active.swap(true, Ordering::SeqCst) {
+        println!("Discovery already running, ignoring start request");
+        return Ok(());
+    }
+
+    // 이전 스캔 스레드가 남아있다면 합류해서 정리
+    if let Some(handle) = state.discovery_handle.lock().take() {
+        let _ = handle.join();
+    }
+
+    // 탐색 결과의 bonded 플래그를 채우기 위해 현재 페어링된 주소 집합을 미리 확보
+    let bonded_addrs: HashSet<String> = android_bluetooth_serial::get_bonded_devices()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|d| d.get_address().ok())
+        .collect();
+
+    let active_flag = Arc::clone(&state.discovery_active);
+    let app_handle_clone = app_handle.clone();
+
+    // 탐색은 자체 스레드에서 실행되어 취소 가능해야 한다.
+    let handle = thread::spawn(move || {
+        println!("Discovery thread started");
+        let scan_flag = Arc::clone(&active_flag);
+        let result = android_bluetooth_serial::start_discovery(move |found: BluetoothDevice| {
+            // stop_discovery_command가 호출된 이후에 도착한 결과는 무시
+            if !scan_flag.load(Ordering::SeqCst) {
+                return;
+            }
+            let address = found.get_address().unwrap_or_default();
+            let name = found.get_name().unwrap_or_else(|_| "Unknown Device".to_string());
+            let rssi = found.get_rssi().unwrap_or(i16::MIN);
+            let bonded = bonded_addrs.contains(&address);
+
+            let _ = app_handle_clone.emit_all(
+                "bluetooth-discovery",
+                Device { address, name, rssi, bonded },
+            );
+        });
+
+        if let Err(e) = result {
+            eprintln!("Discovery error: {}", e);
+        }
+
+        active_flag.store(false, Ordering::SeqCst);
+        println!("Discovery thread finished");
+    });
+
+    *state.discovery_handle.lock() = Some(handle);
+    Ok(())
+}
+
+// 진행 중인 탐색 스캔 취소
+#[tauri::command]
+async fn stop_discovery_command(state: State<'_, Arc<AppState>>) -> Result<(), String> {
+    if !state.discovery_active.swap(false, Ordering::SeqCst) {
+        // 실행 중이 아니었다면 조용히 성공 처리
+        return Ok(());
+    }
+
+    android_bluetooth_serial::stop_discovery().map_err(|e| e.to_string())?;
+
+    if let Some(handle) = state.discovery_handle.lock().take() {
+        let _ = handle.join();
+    }
+
+    Ok(())
+}
+
+// 장치와의 본딩(페어링) 요청
+// 안드로이드 페어링 플로우를 트리거하고, 상태가 바뀔 때마다 bluetooth-bond 이벤트를 발행한다.
+#[tauri::command]
+async fn create_bond_command(address: String, app_handle: tauri::AppHandle) -> Result<(), String> {
+    println!("Requesting bond with: {}", address);
+
+    let address_for_thread = address.clone();
+    thread::spawn(move || {
+        let address_for_events = address_for_thread.clone();
+        let result = android_bluetooth_serial::create_bond(&address_for_thread, move |state| {
+            let _ = app_handle.emit_all(
+                "bluetooth-bond",
+                BondPayload {
+                    address: address_for_events.clone(),
+                    state: bond_state_to_str(state).to_string(),
+                    error: None,
+                },
+            );
+        });
+
+        if let Err(e) = result {
+            eprintln!("Bond request failed for {}: {}", address_for_thread, e);
+        }
+    });
+
+    Ok(())
+}
+
+// 장치와의 본딩 해제
+#[tauri::command]
+async fn remove_bond_command(address: String, app_handle: tauri::AppHandle) -> Result<(), String> {
+    println!("Removing bond with: {}", address);
+
+    android_bluetooth_serial::remove_bond(&address).map_err(|e| {
+        eprintln!("Remove bond failed for {}: {}", address, e);
+        // 실패 시 "bonded"로 단정하지 않고 실제 상태를 조회해서 보고한다.
+        let actual_state = android_bluetooth_serial::get_bond_state(&address)
+            .map(bond_state_to_str)
+            .unwrap_or("none");
+        let _ = app_handle.emit_all(
+            "bluetooth-bond",
+            BondPayload {
+                address: address.clone(),
+                state: actual_state.to_string(),
+                error: Some(e.to_string()),
+            },
+        );
+        e.to_string()
+    })?;
+
+    let _ = app_handle.emit_all(
+        "bluetooth-bond",
+        BondPayload {
+            address: address.clone(),
+            state: "none".to_string(),
+            error: None,
+        },
+    );
+
+    Ok(())
+}
+
+// 현재 본딩 상태 조회
+#[tauri::command]
+async fn get_bond_state_command(address: String) -> Result<String, String> {
+    android_bluetooth_serial::get_bond_state(&address)
+        .map(bond_state_to_str)
+        .map(|s| s.to_string())
+        .map_err(|e| e.to_string())
+}
+
 // 특정 장치에 연결 시도
 // address: 연결할 장치의 MAC 주소
+// reconnect: true이면 연결이 끊어졌을 때 지수 백오프로 자동 재연결을 시도한다
+// framing: 수신 스트림을 메시지 단위로 잘라내는 방법. 생략하면 Raw(기존 동작)로 처리한다.
 // app_handle: 이벤트를 프런트엔드로 보내기 위해 필요
 // state: 소켓 인스턴스를 저장하고 관리하기 위해 필요
 #[tauri::command]
-async fn connect_device_command(address: String, app_handle: tauri::AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+async fn connect_device_command(
+    address: String,
+    reconnect: bool,
+    framing: Option<FramingConfig>,
+    app_handle: tauri::AppHandle,
+    state: State<'_, Arc<AppState>>,
+) -> Result<(), String> {
+    let framing = framing.unwrap_or_default();
+    framing.validate()?;
     println!("Attempting to connect to: {}", address);
 
     // 이미 연결된 상태인지 확인
@@ -85,186 +404,589 @@ async fn connect_device_command(address: String, app_handle: tauri::AppHandle, s
                 address: address.clone(),
                 status: "already_connected".to_string(),
                 error: None,
+                attempt: None,
             });
             return Ok(());
         }
     } // Lock released here
 
-    // 페어링된 장치 목록에서 해당 장치 찾기
-    let bonded_devices = android_bluetooth_serial::get_bonded_devices().map_err(|e| e.to_string())?;
-    let device = bonded_devices.into_iter().find(|d| d.get_address().unwrap_or_default() == address)
-        .ok_or_else(|| "Device not found among bonded devices".to_string())?;
-
-    // RFCOMM 소켓 빌드 (SPP UUID 사용, 보안 연결)
-    let socket = device.build_rfcomm_socket(android_bluetooth_serial::SPP_UUID, true).map_err(|e| e.to_string())?;
-    let arc_socket = Arc::new(Mutex::new(socket));
-
-    // 소켓 연결 시도
-    println!("Calling socket.connect() for {}", address);
-    arc_socket.lock().connect().map_err(|e| {
+    // 소켓을 빌드하고 연결한다 (초기 연결과 재연결 루프가 공유)
+    let socket = connect_socket(&address).map_err(|e| {
         eprintln!("Connection error for {}: {}", address, e);
         // 연결 실패 시 상태 변경 이벤트 발행
         let _ = app_handle.emit_all("bluetooth-status", StatusPayload {
             address: address.clone(),
             status: "connection_failed".to_string(),
-            error: Some(e.to_string()),
+            error: Some(e.clone()),
+            attempt: None,
         });
-        e.to_string() // 오류 반환
+        e
     })?;
 
     println!("Connected successfully to: {}", address);
 
-    // 연결 성공 시 상태에 소켓 저장
-    state.connections.write().insert(address.clone(), Arc::clone(&arc_socket));
+    // 연결 성공 시 상태에 연결 정보 저장 (writer 채널과 종료 플래그; 재연결 여부는 감시 스레드가 보관)
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let (writer_tx, done) = spawn_reader_and_writer(
+        address.clone(), app_handle.clone(), Arc::clone(&shutdown), framing.clone(), socket,
+    );
+    state.connections.write().insert(address.clone(), ConnectionEntry {
+        writer_tx,
+        shutdown: Arc::clone(&shutdown),
+    });
 
     // 연결 성공 상태 이벤트 발행
     let _ = app_handle.emit_all("bluetooth-status", StatusPayload {
         address: address.clone(),
         status: "connected".to_string(),
         error: None,
+        attempt: None,
     });
 
-    // 백그라운드 읽기 스레드 시작
-    let read_socket_arc = Arc::clone(&arc_socket);
+    // 연결 감시(재연결) 스레드 시작 - reader/writer 스레드 자체와는 별개로, reader 스레드가 종료되었을 때
+    // reconnect 여부를 판단해 새로 spawn_reader_and_writer를 호출하는 역할만 한다.
     let address_clone = address.clone();
     let app_handle_clone = app_handle.clone(); // 스레드로 전달할 AppHandle 클론
     let state_arc = state.inner().clone(); // 스레드로 전달할 AppState Arc 클론
 
     thread::spawn(move || {
-        println!("Read thread started for {}", address_clone);
-        let mut read_buf = vec![0u8; 1024]; // 읽기 버퍼
+        connection_supervisor(address_clone, app_handle_clone, state_arc, reconnect, framing, shutdown, done);
+    });
+
+    Ok(())
+}
+
+// address에 해당하는 페어링된 장치를 찾아 RFCOMM 소켓을 만들고 연결한다.
+// 초기 연결과 재연결 루프 양쪽에서 사용하는 공용 로직.
+fn connect_socket(address: &str) -> Result<BluetoothSocket, String> {
+    let bonded_devices = android_bluetooth_serial::get_bonded_devices().map_err(|e| e.to_string())?;
+    let device = bonded_devices.into_iter().find(|d| d.get_address().unwrap_or_default() == address)
+        .ok_or_else(|| "Device not found among bonded devices".to_string())?;
+
+    // RFCOMM 소켓 빌드 (SPP UUID 사용, 보안 연결)
+    let mut socket = device.build_rfcomm_socket(android_bluetooth_serial::SPP_UUID, true).map_err(|e| e.to_string())?;
+    socket.connect().map_err(|e| e.to_string())?;
+    // 읽기 스레드가 쓰기를 절대 막지 않도록 짧은 타임아웃으로 설정 - 타임아웃마다 종료 신호를 확인한다.
+    let _ = socket.set_read_timeout(Some(Duration::from_millis(200)));
+    Ok(socket)
+}
+
+// 연결 하나에 대해 전용 reader 스레드와 writer 스레드를 띄운다.
+// reader는 소켓을 단독으로 소유해 읽고, writer는 복제된 핸들로 쓰기 때문에 더 이상 소켓 뮤텍스를
+// 두고 서로 기다리지 않는다. writer로 보낼 채널의 Sender와, reader 스레드가 끝났을 때 true로
+// 바뀌는 done 플래그를 돌려준다 (감시 스레드가 이 플래그로 재연결 시점을 판단한다).
+fn spawn_reader_and_writer(
+    address: String,
+    app_handle: tauri::AppHandle,
+    shutdown: Arc<AtomicBool>,
+    framing: FramingConfig,
+    mut read_socket: BluetoothSocket,
+) -> (mpsc::Sender<Vec<u8>>, Arc<AtomicBool>) {
+    let write_socket = read_socket.try_clone().expect("failed to clone socket for writer thread");
+    let (writer_tx, writer_rx) = mpsc::channel::<Vec<u8>>();
+    let done = Arc::new(AtomicBool::new(false));
+
+    // writer 스레드: 채널에 쌓인 데이터를 순서대로 쓰고 flush한다.
+    // writer_tx의 마지막 복제본이 드롭되면(재연결로 교체되거나 연결이 제거되면) recv()가 끝나고 자연스럽게 종료된다.
+    let writer_address = address.clone();
+    thread::spawn(move || {
+        let mut socket = write_socket;
+        for data in writer_rx.iter() {
+            if let Err(e) = socket.write_all(&data).and_then(|_| socket.flush()) {
+                eprintln!("Writer thread error for {}: {}", writer_address, e);
+                break;
+            }
+        }
+        println!("Writer thread finished for {}", writer_address);
+    });
+
+    // reader 스레드: 짧은 타임아웃으로 읽고, Raw 모드에서는 도착한 데이터를 즉시, 그 외 프레이밍에서는
+    // 완성된 프레임 단위로 bluetooth-data로 내보낸다.
+    // 매 읽기(최대 200ms 타임아웃)마다 shutdown 플래그를 확인하므로 수동 disconnect가 짧은 지연 안에 반영된다.
+    let reader_address = address;
+    let reader_done = Arc::clone(&done);
+    thread::spawn(move || {
+        println!("Reader thread started for {}", reader_address);
+        let mut read_buf = vec![0u8; 1024];
+        // 프레이밍이 Raw가 아닐 때만 사용하는 재조립 버퍼. 읽은 바이트를 여기 누적했다가
+        // 완전한 프레임만 꺼내 내보내고, 다 맞춰지지 않은 꼬리는 다음 읽기를 위해 남겨둔다.
+        let mut reassembly_buf: Vec<u8> = Vec::new();
         loop {
-            let mut socket = read_socket_arc.lock(); // 소켓에 락 획득
-            match socket.read(&mut read_buf) {
+            if shutdown.load(Ordering::SeqCst) {
+                println!("Reader thread: shutdown requested for {}", reader_address);
+                break;
+            }
+            match read_socket.read(&mut read_buf) {
                 Ok(len) if len > 0 => {
-                    // 데이터 읽기 성공
-                    let data = read_buf[..len].to_vec();
-                    // 데이터를 프런트엔드로 이벤트 발생
-                    let _ = app_handle_clone.emit_all("bluetooth-data", Payload {
-                        address: address_clone.clone(),
-                        data: data,
-                    });
-                    // println!("Read {} bytes from {}", len, address_clone); // 디버그 출력
+                    match &framing {
+                        FramingConfig::Raw => {
+                            // 기존 동작 그대로: 읽은 만큼을 그대로 한 건으로 전달
+                            let _ = app_handle.emit_all("bluetooth-data", Payload {
+                                address: reader_address.clone(),
+                                data: read_buf[..len].to_vec(),
+                                char_uuid: None,
+                            });
+                        }
+                        framing => {
+                            reassembly_buf.extend_from_slice(&read_buf[..len]);
+                            let (frames, frame_error) = extract_frames(&mut reassembly_buf, framing);
+                            for frame in frames {
+                                let _ = app_handle.emit_all("bluetooth-data", Payload {
+                                    address: reader_address.clone(),
+                                    data: frame,
+                                    char_uuid: None,
+                                });
+                            }
+                            if let Some(message) = frame_error {
+                                // 프레임 오류는 연결을 끊지 않고 상태 이벤트로만 알린다.
+                                let _ = app_handle.emit_all("bluetooth-status", StatusPayload {
+                                    address: reader_address.clone(),
+                                    status: "error".to_string(),
+                                    error: Some(message),
+                                    attempt: None,
+                                });
+                            }
+                        }
+                    }
                 }
                 Ok(_) => {
-                    // 0 바이트 읽음 - 연결 끊김 가능성 또는 데이터 없음
-                    // 짧게 대기 후 연결 상태 다시 확인
-                    drop(socket); // 락 해제
-                    thread::sleep(std::time::Duration::from_millis(50));
-                    if !read_socket_arc.lock().is_connected().unwrap_or(false) {
-                        eprintln!("Read thread: Device {} disconnected.", address_clone);
-                        break; // 루프 종료 (스레드 종료)
+                    // 0 바이트 읽음 - 연결 상태를 확인하고 계속 진행
+                    if !read_socket.is_connected().unwrap_or(false) {
+                        eprintln!("Reader thread: Device {} disconnected.", reader_address);
+                        break;
                     }
                 }
                 Err(ref e) if e.kind() == std::io::ErrorKind::TimedOut => {
-                    // 타임아웃 - 데이터가 없거나 읽을 준비가 안 됨. 예상된 상황일 수 있음.
-                    // 연결 상태 확인 후 계속 루프
-                    drop(socket); // 락 해제
-                     if !read_socket_arc.lock().is_connected().unwrap_or(false) {
-                        eprintln!("Read thread (timeout check): Device {} disconnected.", address_clone);
-                        break; // 루프 종료 (스레드 종료)
+                    // 짧은 타임아웃 - 데이터가 없거나 읽을 준비가 안 됨. 예상된 상황.
+                    if !read_socket.is_connected().unwrap_or(false) {
+                        eprintln!("Reader thread (timeout check): Device {} disconnected.", reader_address);
+                        break;
                     }
                 }
                 Err(e) => {
-                    // 다른 읽기 오류 발생 (연결 끊김 포함)
-                    eprintln!("Read thread error for device {}: {}", address_clone, e);
-                    // 오류 상태 이벤트 발행
-                    let _ = app_handle_clone.emit_all("bluetooth-status", StatusPayload {
-                        address: address_clone.clone(),
+                    eprintln!("Reader thread error for device {}: {}", reader_address, e);
+                    let _ = app_handle.emit_all("bluetooth-status", StatusPayload {
+                        address: reader_address.clone(),
                         status: "error".to_string(),
                         error: Some(e.to_string()),
+                        attempt: None,
                     });
-                    break; // 루프 종료 (스레드 종료)
+                    break;
                 }
             }
-            // 중요: 읽기/쓰기 작업 후에는 반드시 소켓 락을 해제하여 다른 명령이 접근할 수 있도록 해야 함.
-            // Ok(len) > 0 케이스에서는 drop(socket)이 없으므로 루프 시작 시 다시 락을 얻음.
-            // 타임아웃이나 0바이트 읽기 케이스는 위에서 drop(socket)을 호출함.
         }
+        let _ = read_socket.close();
+        reader_done.store(true, Ordering::SeqCst);
+        println!("Reader thread finished for {}", reader_address);
+    });
 
-        // 읽기 스레드 종료 전, 상태 관리 맵에서 해당 연결 제거 및 연결 끊김 상태 이벤트 발행
-        println!("Read thread finished for {}. Cleaning up state.", address_clone);
-        state_arc.connections.write().remove(&address_clone); // 상태 맵에서 제거
+    (writer_tx, done)
+}
 
-        // 스레드가 종료되었으므로 연결 끊김 상태 알림 (오류로 종료된 경우 위에서 이미 보냈을 수 있음)
-        let _ = app_handle_clone.emit_all("bluetooth-status", StatusPayload {
-            address: address_clone.clone(),
-            status: "disconnected".to_string(),
-            error: None, // 또는 마지막 오류 정보
-        });
-    });
+// address로 찾은 항목이 여전히 이 감시 스레드가 띄운 연결인지(shutdown 플래그가 같은 Arc인지)
+// 확인한 뒤에만 제거한다. 확인 없이 바로 remove하면, disconnect 이후 같은 address로 재연결이
+// 빠르게 일어났을 때 새 ConnectionEntry를 방금 정리 중이던 옛 감시 스레드가 지워버릴 수 있다.
+fn remove_connection_if_current(
+    connections: &RwLock<HashMap<String, ConnectionEntry>>,
+    address: &str,
+    shutdown: &Arc<AtomicBool>,
+) {
+    let mut lock = connections.write();
+    if lock.get(address).map(|entry| Arc::ptr_eq(&entry.shutdown, shutdown)).unwrap_or(false) {
+        lock.remove(address);
+    }
+}
 
+// 연결 감시 스레드: reader 스레드가 끝날 때까지 기다렸다가(폴링), reconnect가 설정되어
+// 있고 종료 신호가 없는 경우 지수 백오프로 재연결하여 새 reader/writer 쌍을 띄운다.
+// shutdown 플래그가 설정되면(수동 disconnect/cancel_reconnect) 다음 확인 시점(200ms 폴링 중이거나,
+// 재시도 중이면 진행 중인 대기와 연결 시도가 끝난 뒤)에 정리하고 빠져나온다.
+fn connection_supervisor(
+    address: String,
+    app_handle: tauri::AppHandle,
+    state_arc: Arc<AppState>,
+    reconnect: bool,
+    framing: FramingConfig,
+    shutdown: Arc<AtomicBool>,
+    mut done: Arc<AtomicBool>,
+) {
+    'connection: loop {
+        // reader 스레드가 소켓을 직접 소유하므로, 여기서는 짧은 간격으로 reader가 끝났는지(done)를 관찰한다.
+        loop {
+            if shutdown.load(Ordering::SeqCst) {
+                remove_connection_if_current(&state_arc.connections, &address, &shutdown);
+                break 'connection;
+            }
+            if done.load(Ordering::SeqCst) {
+                break; // reader 스레드가 끝났다 - 재연결 여부 판단으로 진행
+            }
+            thread::sleep(Duration::from_millis(200));
+        }
 
-    Ok(())
+        if shutdown.load(Ordering::SeqCst) || !reconnect {
+            println!("Connection supervisor finished for {}. Cleaning up state.", address);
+            remove_connection_if_current(&state_arc.connections, &address, &shutdown);
+            let _ = app_handle.emit_all("bluetooth-status", StatusPayload {
+                address: address.clone(),
+                status: "disconnected".to_string(),
+                error: None,
+                attempt: None,
+            });
+            break 'connection;
+        }
+
+        // 지수 백오프로 재연결 시도: 500ms에서 시작해 실패할 때마다 두 배로, 최대 30초
+        let mut delay_ms: u64 = 500;
+        let mut attempt: u32 = 0;
+        loop {
+            if shutdown.load(Ordering::SeqCst) {
+                remove_connection_if_current(&state_arc.connections, &address, &shutdown);
+                let _ = app_handle.emit_all("bluetooth-status", StatusPayload {
+                    address: address.clone(),
+                    status: "disconnected".to_string(),
+                    error: None,
+                    attempt: None,
+                });
+                break 'connection;
+            }
+
+            attempt += 1;
+            let _ = app_handle.emit_all("bluetooth-status", StatusPayload {
+                address: address.clone(),
+                status: "reconnecting".to_string(),
+                error: None,
+                attempt: Some(attempt),
+            });
+
+            thread::sleep(Duration::from_millis(delay_ms));
+
+            match connect_socket(&address) {
+                Ok(new_socket) => {
+                    let (new_writer_tx, new_done) = spawn_reader_and_writer(
+                        address.clone(), app_handle.clone(), Arc::clone(&shutdown), framing.clone(), new_socket,
+                    );
+                    // 맵에 등록된 writer_tx를 새 채널로 교체 (send_data_command가 즉시 새 연결을 쓰도록)
+                    match state_arc.connections.write().get_mut(&address) {
+                        Some(entry) => entry.writer_tx = new_writer_tx,
+                        None => break 'connection, // 재연결 중 제거됨
+                    }
+                    done = new_done;
+
+                    println!("Reconnected successfully to: {}", address);
+                    let _ = app_handle.emit_all("bluetooth-status", StatusPayload {
+                        address: address.clone(),
+                        status: "connected".to_string(),
+                        error: None,
+                        attempt: None,
+                    });
+
+                    continue 'connection; // 새 reader가 끝날 때까지 다시 관찰, 백오프는 다음 실패 시 500ms부터
+                }
+                Err(e) => {
+                    eprintln!("Reconnect attempt {} for {} failed: {}", attempt, address, e);
+                    delay_ms = (delay_ms * 2).min(30_000);
+                }
+            }
+        }
+    }
 }
 
 // 특정 장치에 데이터 전송
 // address: 데이터를 전송할 장치의 MAC 주소 (HashMap 키)
 // data: 전송할 데이터 바이트 벡터
-// state: 소켓 인스턴스 접근을 위해 필요
+// state: writer 채널 접근을 위해 필요
+// 소켓 락을 기다리지 않고 writer 스레드의 채널에 밀어 넣기만 하므로 reader 스레드와 경합하지 않는다.
 #[tauri::command]
-async fn send_data_command(address: String, data: Vec<u8>, state: State<'_, AppState>) -> Result<(), String> {
+async fn send_data_command(address: String, data: Vec<u8>, state: State<'_, Arc<AppState>>) -> Result<(), String> {
     let connections_lock = state.connections.read();
-    // 해당 장치의 소켓 인스턴스 찾기
-    let socket_arc = connections_lock.get(&address)
+    // 해당 장치의 연결 정보 찾기
+    let entry = connections_lock.get(&address)
         .ok_or_else(|| "Device not connected".to_string())?; // 연결되어 있지 않으면 오류 반환
 
-    let mut socket = socket_arc.lock(); // 소켓에 락 획득
-
-    // 데이터 쓰기
-    socket.write_all(&data).map_err(|e| {
-        eprintln!("Write error for {}: {}", address, e);
-        e.to_string() // 오류 반환
+    entry.writer_tx.send(data).map_err(|_| {
+        eprintln!("Write error for {}: writer thread is not running", address);
+        "Writer thread is not running".to_string()
     })?;
 
-    // 버퍼 비우기 (즉시 전송)
-    socket.flush().map_err(|e| {
-         eprintln!("Flush error for {}: {}", address, e);
-        e.to_string()
-    })?;
-
-    println!("Sent {} bytes to {}", data.len(), address);
     Ok(())
 }
 
 // 특정 장치 연결 해제
 // address: 연결 해제할 장치의 MAC 주소 (HashMap 키)
-// state: 소켓 인스턴스 접근 및 제거를 위해 필요
+// state: 연결 정보 접근 및 제거를 위해 필요
 #[tauri::command]
-async fn disconnect_device_command(address: String, state: State<'_, AppState>) -> Result<(), String> {
+async fn disconnect_device_command(address: String, state: State<'_, Arc<AppState>>) -> Result<(), String> {
     let mut connections_lock = state.connections.write(); // 제거를 위해 쓰기 락 필요
 
-    // 해당 장치의 소켓 인스턴스 찾아서 제거
-    if let Some(socket_arc) = connections_lock.remove(&address) {
-        let mut socket = socket_arc.lock(); // 소켓에 락 획득
+    // 해당 장치의 연결 정보를 찾아서 제거
+    if let Some(entry) = connections_lock.remove(&address) {
+        // shutdown 플래그를 세워 reader/writer 스레드가 스스로 정리하도록 신호한다.
+        // 재연결 루프가 진행 중이었다면 이 플래그로 재시도도 함께 중단된다 - 수동 disconnect가 항상 우선한다.
+        entry.shutdown.store(true, Ordering::SeqCst);
+        println!("Disconnect requested for: {}", address);
+        Ok(())
+    } else {
+        Err("Device not connected".to_string()) // 연결되어 있지 않으면 오류 반환
+    }
+}
+
+// 진행 중인 자동 재연결 루프를 중단하고 연결 항목을 완전히 제거한다.
+// disconnect_device_command와 동일하게 shutdown 플래그로 reader/writer 스레드를 정리한다.
+#[tauri::command]
+async fn cancel_reconnect_command(address: String, state: State<'_, Arc<AppState>>) -> Result<(), String> {
+    let mut connections_lock = state.connections.write();
+
+    match connections_lock.remove(&address) {
+        Some(entry) => {
+            entry.shutdown.store(true, Ordering::SeqCst);
+            println!("Reconnect loop cancelled for: {}", address);
+            Ok(())
+        }
+        None => Err("Device not connected".to_string()),
+    }
+}
+
+// GATT 서비스/특성 탐색 결과를 프런트엔드에 전달하기 위한 구조체
+#[derive(Clone, Serialize)]
+struct GattCharacteristic {
+    uuid: String,
+    properties: Vec<String>, // 예: "read", "write", "notify"
+}
+
+#[derive(Clone, Serialize)]
+struct GattService {
+    uuid: String,
+    characteristics: Vec<GattCharacteristic>,
+}
+
+// BLE GATT 장치에 연결
+// 클래식 RFCOMM(connect_device_command)과 별도의 맵(gatt_connections)에 저장되므로
+// 같은 address라도 두 종류의 링크가 동시에 존재할 수 있다.
+#[tauri::command]
+async fn ble_connect_command(address: String, state: State<'_, Arc<AppState>>) -> Result<(), String> {
+    if state.gatt_connections.read().contains_key(&address) {
+        println!("Already GATT-connected to {}", address);
+        return Ok(());
+    }
+
+    println!("Connecting GATT to: {}", address);
+    let device = android_bluetooth_serial::ble::connect(&address).map_err(|e| e.to_string())?;
+
+    state.gatt_connections.write().insert(
+        address.clone(),
+        Arc::new(Mutex::new(GattConnection { device })),
+    );
+
+    println!("GATT connected: {}", address);
+    Ok(())
+}
+
+// BLE GATT 연결 해제
+// 클래식 쪽의 disconnect_device_command와 마찬가지로 맵에서 항목을 제거하고 장치 핸들을 닫는다.
+#[tauri::command]
+async fn ble_disconnect_command(address: String, state: State<'_, Arc<AppState>>) -> Result<(), String> {
+    let mut gatt_lock = state.gatt_connections.write();
 
-        // 소켓 닫기
-        socket.close().map_err(|e| {
-             eprintln!("Close error for {}: {}", address, e);
+    if let Some(connection) = gatt_lock.remove(&address) {
+        connection.lock().device.disconnect().map_err(|e| {
+            eprintln!("GATT disconnect error for {}: {}", address, e);
             e.to_string()
         })?;
-        println!("Disconnected successfully from: {}", address);
-        // 연결 해제 이벤트는 보통 읽기 스레드 종료 시 발생하지만, 여기서 명시적으로 보낼 수도 있음.
+        println!("GATT disconnected: {}", address);
         Ok(())
     } else {
-        Err("Device not connected".to_string()) // 연결되어 있지 않으면 오류 반환
+        Err("Device not GATT-connected".to_string())
     }
 }
 
+// 연결된 BLE 장치가 제공하는 서비스/특성 목록 탐색
+#[tauri::command]
+async fn ble_discover_services_command(
+    address: String,
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<GattService>, String> {
+    let gatt_lock = state.gatt_connections.read();
+    let connection = gatt_lock.get(&address).ok_or_else(|| "Device not GATT-connected".to_string())?;
+
+    connection.lock().device.discover_services()
+        .map_err(|e| e.to_string())
+        .map(|services| {
+            services.into_iter().map(|service| GattService {
+                uuid: service.uuid,
+                characteristics: service.characteristics.into_iter().map(|c| GattCharacteristic {
+                    uuid: c.uuid,
+                    properties: c.properties,
+                }).collect(),
+            }).collect()
+        })
+}
+
+// 특정 특성(characteristic)에 데이터 쓰기 (예: Nordic UART Service의 TX 특성 6e400002-...)
+#[tauri::command]
+async fn ble_write_char_command(
+    address: String,
+    service_uuid: String,
+    char_uuid: String,
+    data: Vec<u8>,
+    state: State<'_, Arc<AppState>>,
+) -> Result<(), String> {
+    let gatt_lock = state.gatt_connections.read();
+    let connection = gatt_lock.get(&address).ok_or_else(|| "Device not GATT-connected".to_string())?;
+
+    connection.lock().device
+        .write_characteristic(&service_uuid, &char_uuid, &data)
+        .map_err(|e| e.to_string())
+}
+
+// 특정 특성의 알림(notify)을 구독 (예: Nordic UART Service의 RX 특성 6e400003-...)
+// 수신되는 값은 기존 bluetooth-data 채널로 전달되며, char_uuid로 태그되어 프런트엔드가
+// 클래식 시리얼 데이터와 같은 핸들러로 처리할 수 있다.
+#[tauri::command]
+async fn ble_subscribe_command(
+    address: String,
+    service_uuid: String,
+    char_uuid: String,
+    app_handle: tauri::AppHandle,
+    state: State<'_, Arc<AppState>>,
+) -> Result<(), String> {
+    let gatt_lock = state.gatt_connections.read();
+    let connection = gatt_lock.get(&address).ok_or_else(|| "Device not GATT-connected".to_string())?;
+
+    let address_for_events = address.clone();
+    let char_uuid_for_events = char_uuid.clone();
+
+    connection.lock().device
+        .subscribe_characteristic(&service_uuid, &char_uuid, move |data: Vec<u8>| {
+            let _ = app_handle.emit_all("bluetooth-data", Payload {
+                address: address_for_events.clone(),
+                data,
+                char_uuid: Some(char_uuid_for_events.clone()),
+            });
+        })
+        .map_err(|e| e.to_string())
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         // 상태 관리 등록
-        .manage(AppState::default())
+        .manage(Arc::new(AppState::default()))
         // Command 핸들러 등록
         .invoke_handler(tauri::generate_handler![
             is_bluetooth_enabled_command,
             get_bonded_devices_command,
+            start_discovery_command,
+            stop_discovery_command,
+            create_bond_command,
+            remove_bond_command,
+            get_bond_state_command,
             connect_device_command,
             send_data_command,
-            disconnect_device_command
+            disconnect_device_command,
+            cancel_reconnect_command,
+            ble_connect_command,
+            ble_disconnect_command,
+            ble_discover_services_command,
+            ble_write_char_command,
+            ble_subscribe_command
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delimiter_extracts_multiple_frames_from_one_buffer() {
+        let framing = FramingConfig::Delimiter { byte: b'\n' };
+        let mut buf = b"hello\nworld\n".to_vec();
+
+        let (frames, err) = extract_frames(&mut buf, &framing);
+
+        assert!(err.is_none());
+        assert_eq!(frames, vec![b"hello".to_vec(), b"world".to_vec()]);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn delimiter_retains_partial_tail_across_calls() {
+        let framing = FramingConfig::Delimiter { byte: b'\n' };
+        let mut buf = b"hel".to_vec();
+
+        // 첫 번째 읽기: 구분자가 없어 프레임이 만들어지지 않고 버퍼에 그대로 남는다.
+        let (frames, err) = extract_frames(&mut buf, &framing);
+        assert!(frames.is_empty());
+        assert!(err.is_none());
+        assert_eq!(buf, b"hel".to_vec());
+
+        // 두 번째 읽기에서 나머지가 도착하면 누적된 버퍼로 완전한 프레임이 완성된다.
+        buf.extend_from_slice(b"lo\n");
+        let (frames, err) = extract_frames(&mut buf, &framing);
+        assert!(err.is_none());
+        assert_eq!(frames, vec![b"hello".to_vec()]);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn length_prefixed_resets_buffer_on_oversized_frame() {
+        let framing = FramingConfig::LengthPrefixed { header_size: 2, max_frame_len: 4 };
+        // 길이 헤더가 max_frame_len(4)을 초과하는 10을 선언한다.
+        let mut buf = vec![10, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+
+        let (frames, err) = extract_frames(&mut buf, &framing);
+
+        assert!(frames.is_empty());
+        assert!(err.is_some());
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn length_prefixed_decodes_2byte_and_4byte_headers() {
+        let framing_2 = FramingConfig::LengthPrefixed { header_size: 2, max_frame_len: 16 };
+        let mut buf_2 = vec![3, 0, b'a', b'b', b'c'];
+
+        let (frames, err) = extract_frames(&mut buf_2, &framing_2);
+
+        assert!(err.is_none());
+        assert_eq!(frames, vec![b"abc".to_vec()]);
+        assert!(buf_2.is_empty());
+
+        let framing_4 = FramingConfig::LengthPrefixed { header_size: 4, max_frame_len: 16 };
+        let mut buf_4 = vec![3, 0, 0, 0, b'x', b'y', b'z'];
+
+        let (frames, err) = extract_frames(&mut buf_4, &framing_4);
+
+        assert!(err.is_none());
+        assert_eq!(frames, vec![b"xyz".to_vec()]);
+        assert!(buf_4.is_empty());
+    }
+
+    #[test]
+    fn length_prefixed_waits_for_partial_header_and_payload_across_calls() {
+        let framing = FramingConfig::LengthPrefixed { header_size: 2, max_frame_len: 16 };
+        // 헤더조차 다 도착하지 않음 (2바이트 헤더인데 1바이트만 있음)
+        let mut buf = vec![3];
+
+        let (frames, err) = extract_frames(&mut buf, &framing);
+        assert!(frames.is_empty());
+        assert!(err.is_none());
+        assert_eq!(buf, vec![3]);
+
+        // 헤더는 완성됐지만 페이로드(3바이트 중 2바이트)가 아직 덜 도착함
+        buf.extend_from_slice(&[0, b'a', b'b']);
+        let (frames, err) = extract_frames(&mut buf, &framing);
+        assert!(frames.is_empty());
+        assert!(err.is_none());
+        assert_eq!(buf, vec![3, 0, b'a', b'b']);
+
+        // 나머지 페이로드가 도착하면 비로소 프레임이 완성된다.
+        buf.push(b'c');
+        let (frames, err) = extract_frames(&mut buf, &framing);
+        assert!(err.is_none());
+        assert_eq!(frames, vec![b"abc".to_vec()]);
+        assert!(buf.is_empty());
+    }
+}
+